@@ -1,14 +1,27 @@
 use reqwest::Client;
-use reqwest::header::ACCEPT;
+use reqwest::header::{ACCEPT, RETRY_AFTER};
+use reqwest::StatusCode;
+use std::collections::HashMap;
 use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use serde::{Serialize, Deserialize};
-use log::info;
-
-// API KEY
-pub const API_KEY: &str = "";
+use log::{debug, info};
+use futures::stream::{self, Stream, StreamExt};
+
+// `std::time::Instant`/`SystemTime::now()` panic at runtime on bare
+// `wasm32-unknown-unknown` (no clock syscall to back them). `web-time`
+// mirrors the same API on top of `Performance.now()`/`Date.now()` via
+// wasm-bindgen, and is a plain passthrough to `std::time` everywhere else.
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+#[cfg(target_arch = "wasm32")]
+use web_time::{Instant, SystemTime, UNIX_EPOCH};
 
 /*
-    Base URL for making GET requests to the Cielo API
+    Default base URL for making GET requests to the Cielo API. Override via
+    `CieloClientBuilder::base_url` (useful for pointing at a mock/staging
+    feed in tests).
 */
 pub const BASE_URL: &str = "https://feed-api.cielo.finance/api/v1/feed?";
 
@@ -108,192 +121,673 @@ pub struct CieloRequest {
 }
 
 /*
-    passing a CieloRequest object to this function will return
-    data from the Cielo API corresponding to the information
-    specified in the CieloRequest object.
+    A cached response body, along with when it was fetched so staleness can
+    be checked against `CieloClient::cache_ttl`.
 */
-pub async fn submit_cielo_get_request(req: CieloRequest) ->
-Result<String, Box<dyn Error + Send + Sync>> {
-    let client = Client::new();
-
-    let mut chains_string = String::new();
-    let mut index = 0;
-    for chain in &req.chains.clone().unwrap() {
-        if index != req.chains.clone().unwrap().len() {
-            chains_string.push_str(chain.to_get_format());
-            chains_string.push(',');
-        } else {
-            chains_string.push_str(chain.to_get_format());
+#[derive(Clone, Debug)]
+struct CacheEntry {
+    body: String,
+    fetched_at: Instant,
+}
+
+/*
+    Whether a cache entry fetched at `fetched_at` is still fresh enough to
+    serve in place of a live request, given `ttl`.
+*/
+fn cache_entry_is_fresh(fetched_at: Instant, ttl: Duration) -> bool {
+    fetched_at.elapsed() < ttl
+}
+
+/*
+    Default number of attempts (including the first) made against the feed
+    before giving up on a connection error or a 429/5xx response.
+*/
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/*
+    Default base delay for the exponential backoff between retries.
+*/
+const DEFAULT_RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+
+/*
+    Sleep used by the retry/watch loops below: `tokio`'s timer on native
+    targets, `gloo-timers` on `wasm32-unknown-unknown`, where `tokio`'s
+    timer isn't available.
+*/
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await;
+}
+
+/*
+    Whether a response status is worth retrying: rate-limited or a server
+    error. Anything else (2xx, 4xx other than 429) is final.
+*/
+fn is_retriable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/*
+    Filters `transactions` down to the ones not already present in `seen`
+    (keyed by `chain:tx_hash`, stable across overlapping poll windows),
+    inserting the fresh ones into `seen` and advancing `watermark` to the
+    newest timestamp observed. Used by `watch()` to turn a raw page of
+    polled transactions into the deduplicated, in-order tail it emits.
+
+    `from_timestamp` already excludes anything strictly older than the
+    watermark, so a re-delivered id can only ever collide at the current
+    watermark timestamp itself (the boundary of the previous poll window).
+    `seen` is pruned to just those ids after each batch rather than
+    retaining every id ever observed, so it stays bounded across an
+    unattended, long-running `watch()` stream.
+*/
+fn dedupe_and_advance_watermark(
+    transactions: Vec<Transaction>,
+    seen: &mut std::collections::HashMap<String, i64>,
+    watermark: &mut Option<i64>,
+) -> Vec<Transaction> {
+    let mut fresh = Vec::new();
+
+    for tx in transactions {
+        let id = format!("{}:{}", tx.chain, tx.tx_hash);
+        if seen.contains_key(&id) {
+            continue;
         }
-        index += 1;
+
+        *watermark = Some(watermark.map_or(tx.timestamp, |wm| wm.max(tx.timestamp)));
+        seen.insert(id, tx.timestamp);
+        fresh.push(tx);
     }
 
-    let mut tx_type_string = String::new();
-    index = 0;
-    for tx_type in &req.types.clone().unwrap() {
-        if index!= req.types.clone().unwrap().len() {
-            tx_type_string.push_str(tx_type.to_get_format());
-            tx_type_string.push('&');
-        } else {
-            tx_type_string.push_str(tx_type.to_get_format());
+    if let Some(wm) = *watermark {
+        seen.retain(|_, ts| *ts == wm);
+    }
+
+    fresh
+}
+
+/*
+    A reusable handle to the Cielo feed API. Owns the underlying
+    `reqwest::Client` (and thus its connection pool) so callers don't pay
+    for a fresh TCP/TLS handshake on every request, plus the API key and
+    base URL to query against. Build one with `CieloClient::builder()`.
+
+    When `cache_ttl` is set, identical requests (same constructed query URL)
+    within the TTL are served from an in-memory cache instead of hitting
+    `feed-api.cielo.finance`; an entry is refreshed lazily on the first
+    access after it goes stale. The cache is shared across clones, so a
+    cloned client (as used internally by `fetch_all_transactions`) still
+    benefits from entries warmed by the original.
+
+    Connection errors and 429/5xx responses are retried, up to `max_attempts`
+    attempts in all (the first one included), with exponential backoff
+    (jittered) starting at `retry_base_delay`, honoring a `Retry-After`
+    header when the feed sends one.
+*/
+#[derive(Clone)]
+pub struct CieloClient {
+    http: Client,
+    api_key: String,
+    base_url: String,
+    cache_ttl: Option<Duration>,
+    cache: Arc<Mutex<HashMap<String, CacheEntry>>>,
+    max_attempts: u32,
+    retry_base_delay: Duration,
+}
+
+// Manual impl so the API key never leaks through `{:?}`/`log::debug!("{:?}", ...)`.
+impl std::fmt::Debug for CieloClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CieloClient")
+            .field("http", &self.http)
+            .field("api_key", &"<redacted>")
+            .field("base_url", &self.base_url)
+            .field("cache_ttl", &self.cache_ttl)
+            .field("cache", &self.cache)
+            .field("max_attempts", &self.max_attempts)
+            .field("retry_base_delay", &self.retry_base_delay)
+            .finish()
+    }
+}
+
+impl CieloClient {
+    /*
+        Starts building a `CieloClient`. At minimum, set an API key before
+        calling `build()`.
+    */
+    pub fn builder() -> CieloClientBuilder {
+        CieloClientBuilder::default()
+    }
+
+    /*
+        passing a CieloRequest object to this method will return
+        data from the Cielo API corresponding to the information
+        specified in the CieloRequest object.
+    */
+    pub async fn submit_cielo_get_request(&self, req: CieloRequest) ->
+    Result<String, Box<dyn Error + Send + Sync>> {
+        let _q_url =
+            self.construct_url_from_req_object(req)
+            .await
+            .expect("Error constructing GET request query values (URL)");
+
+        if let Some(ttl) = self.cache_ttl {
+            let cached = self.cache.lock().unwrap().get(&_q_url).cloned();
+            if let Some(entry) = cached {
+                if cache_entry_is_fresh(entry.fetched_at, ttl) {
+                    return Ok(entry.body);
+                }
+            }
         }
-        index += 1;
+
+        let body = self.send_with_retry(&_q_url).await?;
+        debug!("{}", body);
+
+        if self.cache_ttl.is_some() {
+            self.cache.lock().unwrap().insert(
+                _q_url,
+                CacheEntry { body: body.clone(), fetched_at: Instant::now() },
+            );
+        }
+
+        Ok(body)
     }
 
-    let mut _q_url = 
-        construct_url_from_req_object(req)
-        .await
-        .expect("Error constructing GET request query values (URL)");
+    /*
+        Sends the GET request, retrying on connection errors and on
+        429/5xx responses, for up to `max_attempts` attempts in all
+        (the first one included). A `Retry-After`
+        header on a 429/5xx response takes priority over the computed
+        backoff delay.
+    */
+    async fn send_with_retry(&self, url: &str) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let mut attempt = 1;
+
+        loop {
+            let sent = self.http
+                .get(url)
+                .header(ACCEPT, "application/json")
+                .header("X-API-KEY", &self.api_key)
+                .send().await;
+
+            match sent {
+                Ok(response) => {
+                    let status = response.status();
+
+                    if status.is_success() {
+                        return Ok(response.text().await?);
+                    }
+
+                    if !is_retriable_status(status) || attempt >= self.max_attempts {
+                        let body = response.text().await.unwrap_or_default();
+                        return Err(format!(
+                            "Cielo API request failed with {}: {}",
+                            status, body
+                        ).into());
+                    }
+
+                    let delay = response
+                        .headers()
+                        .get(RETRY_AFTER)
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| value.parse::<u64>().ok())
+                        .map(Duration::from_secs)
+                        .unwrap_or_else(|| self.backoff_delay(attempt));
+
+                    sleep(delay).await;
+                }
+                Err(e) => {
+                    let retriable = e.is_connect() || e.is_timeout() || e.is_request();
+
+                    if !retriable || attempt >= self.max_attempts {
+                        return Err(e.into());
+                    }
+
+                    sleep(self.backoff_delay(attempt)).await;
+                }
+            }
 
-    let response = client
-        .get(_q_url)
-        .header(ACCEPT, "application/json")
-        .header("X-API-KEY", API_KEY)
-        .send().await?;
+            attempt += 1;
+        }
+    }
 
-    let body = response.text().await?;
-    println!("{}", body);   
+    /*
+        Exponential backoff (base delay doubling per attempt) with up to
+        25% jitter, so retrying clients polling in lockstep don't all
+        retry on the same tick.
+    */
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(16);
+        let backoff = self.retry_base_delay.saturating_mul(1u32 << exponent);
+
+        let jitter_nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+        let jitter_fraction = (jitter_nanos % 250) as f64 / 1000.0;
+
+        backoff.mul_f64(1.0 + jitter_fraction)
+    }
+}
 
-    Ok(body)
+/*
+    Builder for `CieloClient`. Mirrors the builder pattern used by
+    `reqwest::ClientBuilder` itself: configure, then `build()`.
+*/
+#[derive(Default)]
+pub struct CieloClientBuilder {
+    api_key: Option<String>,
+    base_url: Option<String>,
+    cache_ttl: Option<Duration>,
+    max_attempts: Option<u32>,
+    retry_base_delay: Option<Duration>,
 }
 
-async fn construct_url_from_req_object(request: CieloRequest) -> Result<String, Box<dyn Error>> {
-    let mut _q_url = String::new();
-    
-    // Add base URL to the constructed URL
-    _q_url.push_str(BASE_URL);
-
-    // wallet address
-    if let Some(wallet) = request.wallet {
-        let url_slice: String = format!("wallet={}", wallet);
-        _q_url.push_str(&url_slice);
-        info!("URL CONSTRUCTOR:\n \x1b[35mAdding wallet address:\x1b[0m {}", _q_url);
-    } else {
-        return Err("Wallet address is required".into());
+impl CieloClientBuilder {
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
     }
 
-    // tx limit
-    if let Some(limit) = request.limit {
-        let url_slice: String = format!("&limit={}", limit);
-        _q_url.push_str(&url_slice);
-        info!("URL CONSTRUCTOR:\n \x1b[35mAdding tx limit:\x1b[0m {}", _q_url);
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
     }
 
-    // list id
-    if let Some(list) = request.list {
-        let url_slice: String = format!("&list={}", list);
-        _q_url.push_str(&url_slice);
-        info!("URL CONSTRUCTOR:\n \x1b[35mAdding list ID:\x1b[0m {}", _q_url);
+    /*
+        Enables the in-memory response cache: an identical request made
+        again within `ttl` is served from memory instead of hitting the
+        Cielo feed. Off by default.
+    */
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
     }
 
-    // chains 
-    if let Some(chains) = &request.chains { 
-        
-        let mut index: usize = 0;
-        for chain in chains {
-            if index == 0 {
-                let url_slice: String = format!("&chains={}", chain.to_get_format());
-                _q_url.push_str(&url_slice);
-            } else {
-                let url_slice: String = format!(",{}", chain.to_get_format());
-                _q_url.push_str(&url_slice);
-            }
+    /*
+        Maximum attempts (including the first) made against the feed before
+        giving up on a connection error or a 429/5xx response. Defaults to
+        `DEFAULT_MAX_ATTEMPTS`.
+    */
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
 
-            index += 1;
-        }  
+    /*
+        Base delay for the exponential backoff between retries. Defaults to
+        `DEFAULT_RETRY_BASE_DELAY`.
+    */
+    pub fn retry_base_delay(mut self, base_delay: Duration) -> Self {
+        self.retry_base_delay = Some(base_delay);
+        self
+    }
 
-        info!("URL CONSTRUCTOR:\n \x1b[35mAdding chains:\x1b[0m {}", _q_url);
+    pub fn build(self) -> CieloClient {
+        CieloClient {
+            http: Client::new(),
+            api_key: self.api_key.unwrap_or_default(),
+            base_url: self.base_url.unwrap_or_else(|| BASE_URL.to_string()),
+            cache_ttl: self.cache_ttl,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+            max_attempts: self.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS),
+            retry_base_delay: self.retry_base_delay.unwrap_or(DEFAULT_RETRY_BASE_DELAY),
+        }
     }
+}
+
+/*
+    Paging cursor returned alongside a page of feed data. `next_object_id`
+    is the value to feed back into `CieloRequest::start_from` to fetch the
+    following page; it is absent once the feed is exhausted.
+*/
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Paging {
+    pub next_object_id: Option<String>,
+    pub has_next_page: Option<bool>,
+}
 
-    // tx types
-    if let Some(types) = &request.types {
+/*
+    A single transaction from the Cielo feed. Covers the fields common to
+    every tx type; type-specific fields beyond these are not modeled yet.
+*/
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+    pub wallet: String,
+    pub chain: String,
+    pub tx_hash: String,
+    #[serde(rename = "type")]
+    pub tx_type: String,
+    pub timestamp: i64,
+    pub token0_symbol: Option<String>,
+    pub token0_amount: Option<f64>,
+    pub token0_amount_usd: Option<f64>,
+    pub token1_symbol: Option<String>,
+    pub token1_amount: Option<f64>,
+    pub token1_amount_usd: Option<f64>,
+}
 
-        let mut index: usize = 0;
-        for tx_type in types {
-            if index == 0 {
-                let url_slice: String = format!("&txTypes={}", tx_type.to_get_format());
-                _q_url.push_str(&url_slice);
-            } else {
-                let url_slice: String = format!(",{}", tx_type.to_get_format());
-                _q_url.push_str(&url_slice);
-            }
+/*
+    The `data` object of a Cielo feed response: the page of transactions
+    plus the paging cursor for fetching the next page.
+*/
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FeedData {
+    pub items: Vec<Transaction>,
+    pub paging: Paging,
+}
+
+/*
+    Typed deserialization of the raw body returned by
+    `submit_cielo_get_request`.
+*/
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CieloResponse {
+    pub status: Option<String>,
+    pub data: FeedData,
+}
 
-            index += 1;
+impl CieloClient {
+    /*
+        Fetches a single page and hands back its transactions alongside the
+        paging cursor for the next one, if any.
+    */
+    async fn fetch_page(
+        &self,
+        req: CieloRequest,
+    ) -> Result<(Vec<Transaction>, Option<String>), Box<dyn Error + Send + Sync>> {
+        let body = self.submit_cielo_get_request(req).await?;
+        let parsed: CieloResponse = serde_json::from_str(&body)?;
+
+        Ok((parsed.data.items, parsed.data.paging.next_object_id))
+    }
+
+    /*
+        Like `fetch_page`, but always goes over the wire: `watch()`'s poll
+        URL is frequently identical across ticks (the watermark hasn't
+        advanced), and routing that through the shared response cache would
+        mean a quiet period keeps serving the same stale page for the whole
+        `cache_ttl` instead of re-checking the feed.
+    */
+    async fn fetch_page_uncached(
+        &self,
+        req: CieloRequest,
+    ) -> Result<(Vec<Transaction>, Option<String>), Box<dyn Error + Send + Sync>> {
+        let url = self
+            .construct_url_from_req_object(req)
+            .await
+            .expect("Error constructing GET request query values (URL)");
+
+        let body = self.send_with_retry(&url).await?;
+        debug!("{}", body);
+
+        let parsed: CieloResponse = serde_json::from_str(&body)?;
+
+        Ok((parsed.data.items, parsed.data.paging.next_object_id))
+    }
+
+    /*
+        Given a `CieloRequest`, repeatedly fetches pages from the Cielo feed,
+        threading `paging.next_object_id` into `start_from` on each following
+        request, and yields transactions as a single flattened stream. Fetching
+        stops once the cursor comes back empty, once `page_limit` pages have
+        been fetched (if set), or after a page fails to fetch/parse, in which
+        case the error is yielded as the final item.
+    */
+    pub fn fetch_all_transactions(
+        &self,
+        req: CieloRequest,
+        page_limit: Option<usize>,
+    ) -> impl Stream<Item = Result<Transaction, Box<dyn Error + Send + Sync>>> {
+        struct PageState {
+            client: CieloClient,
+            next_req: Option<CieloRequest>,
+            pages_fetched: usize,
         }
 
-        info!("URL CONSTRUCTOR:\n \x1b[35mAdding tx types:\x1b[0m {}", _q_url);
+        let pages = stream::unfold(
+            PageState { client: self.clone(), next_req: Some(req), pages_fetched: 0 },
+            move |mut state| async move {
+                let req = state.next_req.take()?;
+
+                if page_limit.is_some_and(|limit| state.pages_fetched >= limit) {
+                    return None;
+                }
+
+                let next_cursor_req = req.clone();
+                let page_result = state.client.fetch_page(req).await;
+                state.pages_fetched += 1;
+
+                match page_result {
+                    Ok((transactions, next_object_id)) => {
+                        state.next_req = next_object_id
+                            .filter(|id| !id.is_empty())
+                            .map(|id| CieloRequest { start_from: Some(id), ..next_cursor_req });
+                        Some((Ok(transactions), state))
+                    }
+                    Err(e) => Some((Err(e), state)),
+                }
+            },
+        );
+
+        pages.flat_map(|page_result| {
+            let items = match page_result {
+                Ok(transactions) => transactions.into_iter().map(Ok).collect::<Vec<_>>(),
+                Err(e) => vec![Err(e)],
+            };
+            stream::iter(items)
+        })
     }
 
-    // tokens
-    if let Some(tokens) = &request.tokens {
+    /*
+        Emulates a push feed by polling `req` every `poll_interval`. Each
+        tick, `from_timestamp` is set to the newest timestamp delivered so
+        far and `new_trades` is forced to `true`, so only transactions at or
+        after the current watermark are fetched; transactions already
+        delivered (by chain + tx hash) are dropped so overlapping windows at
+        the boundary timestamp don't double-emit. The watermark advances to
+        the newest timestamp seen each poll. Transient HTTP errors surface
+        as `Err` items without ending the stream.
+    */
+    pub fn watch(
+        &self,
+        req: CieloRequest,
+        poll_interval: Duration,
+    ) -> impl Stream<Item = Result<Transaction, Box<dyn Error + Send + Sync>>> {
+        struct WatchState {
+            client: CieloClient,
+            req: CieloRequest,
+            poll_interval: Duration,
+            watermark: Option<i64>,
+            seen: std::collections::HashMap<String, i64>,
+            first_tick: bool,
+        }
 
-        let mut index: usize = 0;
-        for token in tokens {
-            if index == 0 {
-                let url_slice: String = format!("&tokens={}", token);
-                _q_url.push_str(&url_slice);
+        // Honor a caller-supplied `from_timestamp` as the starting point
+        // instead of throwing it away on the first poll tick.
+        let watermark = req.from_timestamp;
+
+        let state = WatchState {
+            client: self.clone(),
+            req,
+            poll_interval,
+            watermark,
+            seen: std::collections::HashMap::new(),
+            first_tick: true,
+        };
+
+        stream::unfold(state, move |mut state| async move {
+            if state.first_tick {
+                state.first_tick = false;
             } else {
-                let url_slice: String = format!(",{}", token);
-                _q_url.push_str(&url_slice);
+                sleep(state.poll_interval).await;
             }
 
-            index += 1;
-        }
-
-        info!("URL CONSTRUCTOR:\n \x1b[35mAdding tokens:\x1b[0m {}", _q_url);
+            let mut req = state.req.clone();
+            req.new_trades = Some(true);
+            req.from_timestamp = state.watermark;
+
+            match state.client.fetch_page_uncached(req).await {
+                Ok((transactions, _next_object_id)) => {
+                    let fresh = dedupe_and_advance_watermark(transactions, &mut state.seen, &mut state.watermark)
+                        .into_iter()
+                        .map(Ok)
+                        .collect();
+                    Some((fresh, state))
+                }
+                Err(e) => Some((vec![Err(e)], state)),
+            }
+        })
+        .flat_map(stream::iter)
     }
 
-    // min usd
-    if let Some(min_usd) = request.min_usd {
-        let url_slice: String = format!("&minUSD={}", min_usd);
-        _q_url.push_str(&url_slice);
+    async fn construct_url_from_req_object(&self, request: CieloRequest) -> Result<String, Box<dyn Error>> {
+        let mut _q_url = String::new();
 
-        info!("URL CONSTRUCTOR:\n \x1b[35mAdding min USD amount for txs:\x1b[0m {}", _q_url);
-    }
+        // Add base URL to the constructed URL
+        _q_url.push_str(&self.base_url);
 
-    // new trades
-    if let Some(new_trades) = request.new_trades {
-        if new_trades {
-            _q_url.push_str("&newTrades=true");
+        // wallet address
+        if let Some(wallet) = request.wallet {
+            let url_slice: String = format!("wallet={}", wallet);
+            _q_url.push_str(&url_slice);
+            info!("URL CONSTRUCTOR:\n \x1b[35mAdding wallet address:\x1b[0m {}", _q_url);
         } else {
-            _q_url.push_str("&newTrades=false");
+            return Err("Wallet address is required".into());
         }
 
-        info!("URL CONSTRUCTOR:\n \x1b[35mAdding new trades filter:\x1b[0m {}", _q_url);
-    }  
+        // tx limit
+        if let Some(limit) = request.limit {
+            let url_slice: String = format!("&limit={}", limit);
+            _q_url.push_str(&url_slice);
+            info!("URL CONSTRUCTOR:\n \x1b[35mAdding tx limit:\x1b[0m {}", _q_url);
+        }
 
-    // start from
-    if let Some(start_from) = &request.start_from {
-        let url_slice = format!("&startFrom={}", start_from);
-        _q_url.push_str(&url_slice);
+        // list id
+        if let Some(list) = request.list {
+            let url_slice: String = format!("&list={}", list);
+            _q_url.push_str(&url_slice);
+            info!("URL CONSTRUCTOR:\n \x1b[35mAdding list ID:\x1b[0m {}", _q_url);
+        }
 
-        info!(
-            "URL CONSTRUCTOR:\n \x1b[35mstartFrom value for response `paging.next_object_id` to get next page:\x1b[0m {}",
-             _q_url
-        );
-    }
+        // chains 
+        if let Some(chains) = &request.chains { 
+        
+            let mut index: usize = 0;
+            for chain in chains {
+                if index == 0 {
+                    let url_slice: String = format!("&chains={}", chain.to_get_format());
+                    _q_url.push_str(&url_slice);
+                } else {
+                    let url_slice: String = format!(",{}", chain.to_get_format());
+                    _q_url.push_str(&url_slice);
+                }
+
+                index += 1;
+            }  
+
+            info!("URL CONSTRUCTOR:\n \x1b[35mAdding chains:\x1b[0m {}", _q_url);
+        }
 
-    // from timestamp
-    if let Some(from_timestamp) = request.from_timestamp {
-        let url_slice = format!("&fromTimestamp={}", from_timestamp);
-        _q_url.push_str(&url_slice);
+        // tx types
+        if let Some(types) = &request.types {
 
-        info!("URL CONSTRUCTOR:\n \x1b[35mAdding from_timestamp (UTC):\x1b[0m {}", _q_url);
-    }
+            let mut index: usize = 0;
+            for tx_type in types {
+                if index == 0 {
+                    let url_slice: String = format!("&txTypes={}", tx_type.to_get_format());
+                    _q_url.push_str(&url_slice);
+                } else {
+                    let url_slice: String = format!(",{}", tx_type.to_get_format());
+                    _q_url.push_str(&url_slice);
+                }
+
+                index += 1;
+            }
+
+            info!("URL CONSTRUCTOR:\n \x1b[35mAdding tx types:\x1b[0m {}", _q_url);
+        }
+
+        // tokens
+        if let Some(tokens) = &request.tokens {
+
+            let mut index: usize = 0;
+            for token in tokens {
+                if index == 0 {
+                    let url_slice: String = format!("&tokens={}", token);
+                    _q_url.push_str(&url_slice);
+                } else {
+                    let url_slice: String = format!(",{}", token);
+                    _q_url.push_str(&url_slice);
+                }
+
+                index += 1;
+            }
+
+            info!("URL CONSTRUCTOR:\n \x1b[35mAdding tokens:\x1b[0m {}", _q_url);
+        }
 
-    // to timestamp
-    if let Some(to_timestamp) = request.to_timestamp {
-        let url_slice = format!("&toTimestamp={}", to_timestamp);
-        _q_url.push_str(&url_slice);
+        // min usd
+        if let Some(min_usd) = request.min_usd {
+            let url_slice: String = format!("&minUSD={}", min_usd);
+            _q_url.push_str(&url_slice);
+
+            info!("URL CONSTRUCTOR:\n \x1b[35mAdding min USD amount for txs:\x1b[0m {}", _q_url);
+        }
+
+        // new trades
+        if let Some(new_trades) = request.new_trades {
+            if new_trades {
+                _q_url.push_str("&newTrades=true");
+            } else {
+                _q_url.push_str("&newTrades=false");
+            }
+
+            info!("URL CONSTRUCTOR:\n \x1b[35mAdding new trades filter:\x1b[0m {}", _q_url);
+        }  
+
+        // start from
+        if let Some(start_from) = &request.start_from {
+            let url_slice = format!("&startFrom={}", start_from);
+            _q_url.push_str(&url_slice);
+
+            info!(
+                "URL CONSTRUCTOR:\n \x1b[35mstartFrom value for response `paging.next_object_id` to get next page:\x1b[0m {}",
+                 _q_url
+            );
+        }
+
+        // from timestamp
+        if let Some(from_timestamp) = request.from_timestamp {
+            let url_slice = format!("&fromTimestamp={}", from_timestamp);
+            _q_url.push_str(&url_slice);
+
+            info!("URL CONSTRUCTOR:\n \x1b[35mAdding from_timestamp (UTC):\x1b[0m {}", _q_url);
+        }
+
+        // to timestamp
+        if let Some(to_timestamp) = request.to_timestamp {
+            let url_slice = format!("&toTimestamp={}", to_timestamp);
+            _q_url.push_str(&url_slice);
         
-        info!("URL CONSTRUCTOR:\n \x1b[35mAdding to_timestamp (UTC):\x1b[0m {}", _q_url);
-    }
+            info!("URL CONSTRUCTOR:\n \x1b[35mAdding to_timestamp (UTC):\x1b[0m {}", _q_url);
+        }
 
-    Ok(_q_url)
+        Ok(_q_url)
+    }
 }
 
 
-#[cfg(test)]
+// tokio::test drives these against the real feed, which isn't available
+// (and tokio isn't compiled in) when targeting wasm32-unknown-unknown.
+#[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
     use super::*;
 
@@ -314,7 +808,8 @@ mod tests {
             to_timestamp: None,
         };
 
-        submit_cielo_get_request(request).await.expect("Error getting transactions");
+        let client = CieloClient::builder().api_key("").build();
+        client.submit_cielo_get_request(request).await.expect("Error getting transactions");
     }
 
     // sol test
@@ -334,7 +829,126 @@ mod tests {
             to_timestamp: None,
         };
 
-        submit_cielo_get_request(request).await.expect("Error getting transactions");
+        let client = CieloClient::builder().api_key("").build();
+        client.submit_cielo_get_request(request).await.expect("Error getting transactions");
+    }
+
+    fn test_transaction(chain: &str, tx_hash: &str, timestamp: i64) -> Transaction {
+        Transaction {
+            wallet: "wallet".to_string(),
+            chain: chain.to_string(),
+            tx_hash: tx_hash.to_string(),
+            tx_type: "swap".to_string(),
+            timestamp,
+            token0_symbol: None,
+            token0_amount: None,
+            token0_amount_usd: None,
+            token1_symbol: None,
+            token1_amount: None,
+            token1_amount_usd: None,
+        }
+    }
+
+    #[test]
+    fn dedupe_and_advance_watermark_drops_repeats_and_tracks_latest_timestamp() {
+        let mut seen = std::collections::HashMap::new();
+        let mut watermark = None;
+
+        let first_batch = vec![
+            test_transaction("ethereum", "0xa", 100),
+            test_transaction("ethereum", "0xb", 105),
+        ];
+        let fresh = dedupe_and_advance_watermark(first_batch, &mut seen, &mut watermark);
+        assert_eq!(fresh.len(), 2);
+        assert_eq!(watermark, Some(105));
+
+        // Overlapping poll window: 0xb reappears at the boundary timestamp,
+        // alongside one genuinely new transaction.
+        let second_batch = vec![
+            test_transaction("ethereum", "0xb", 105),
+            test_transaction("ethereum", "0xc", 110),
+        ];
+        let fresh = dedupe_and_advance_watermark(second_batch, &mut seen, &mut watermark);
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].tx_hash, "0xc");
+        assert_eq!(watermark, Some(110));
+    }
+
+    #[test]
+    fn dedupe_and_advance_watermark_keys_by_chain_and_hash() {
+        let mut seen = std::collections::HashMap::new();
+        let mut watermark = None;
+
+        // Same tx hash on two different chains must not be treated as a dup.
+        let batch = vec![
+            test_transaction("ethereum", "0xabc", 1),
+            test_transaction("polygon", "0xabc", 2),
+        ];
+        let fresh = dedupe_and_advance_watermark(batch, &mut seen, &mut watermark);
+        assert_eq!(fresh.len(), 2);
+    }
+
+    #[test]
+    fn dedupe_and_advance_watermark_prunes_seen_to_the_current_watermark() {
+        let mut seen = std::collections::HashMap::new();
+        let mut watermark = None;
+
+        // Once the watermark advances past 100, only ids tied to the new
+        // watermark timestamp (105) can ever recur, so older ids should be
+        // dropped from `seen` rather than retained forever.
+        let batch = vec![
+            test_transaction("ethereum", "0xa", 100),
+            test_transaction("ethereum", "0xb", 105),
+        ];
+        dedupe_and_advance_watermark(batch, &mut seen, &mut watermark);
+        assert_eq!(watermark, Some(105));
+        assert_eq!(seen.len(), 1);
+        assert!(seen.contains_key("ethereum:0xb"));
+    }
+
+    #[test]
+    fn cache_entry_is_fresh_within_ttl_and_stale_after() {
+        let ttl = Duration::from_millis(50);
+        let fetched_at = Instant::now();
+
+        assert!(cache_entry_is_fresh(fetched_at, ttl));
+
+        std::thread::sleep(Duration::from_millis(75));
+        assert!(!cache_entry_is_fresh(fetched_at, ttl));
+    }
+
+    #[test]
+    fn is_retriable_status_covers_429_and_5xx_only() {
+        assert!(is_retriable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retriable_status(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(is_retriable_status(StatusCode::SERVICE_UNAVAILABLE));
+
+        assert!(!is_retriable_status(StatusCode::OK));
+        assert!(!is_retriable_status(StatusCode::NOT_FOUND));
+        assert!(!is_retriable_status(StatusCode::BAD_REQUEST));
+    }
+
+    #[test]
+    fn backoff_delay_grows_exponentially_with_base_delay_and_caps_the_exponent() {
+        let base_delay = Duration::from_millis(100);
+        let client = CieloClient::builder()
+            .api_key("")
+            .retry_base_delay(base_delay)
+            .build();
+
+        // Each attempt's delay should be within [base * 2^(n-1), base * 2^(n-1) * 1.25)
+        // (the jitter adds up to 25% on top of the unjittered backoff).
+        for attempt in 1..=5u32 {
+            let unjittered = base_delay.saturating_mul(1u32 << (attempt - 1));
+            let delay = client.backoff_delay(attempt);
+            assert!(delay >= unjittered, "attempt {attempt}: {delay:?} < {unjittered:?}");
+            assert!(delay < unjittered.mul_f64(1.25), "attempt {attempt}: {delay:?} >= {unjittered:?} * 1.25");
+        }
+
+        // The exponent is capped at 16 doublings, so very high attempt counts
+        // shouldn't keep growing without bound.
+        let capped = client.backoff_delay(100);
+        let max_uncapped = base_delay.saturating_mul(1u32 << 16);
+        assert!(capped < max_uncapped.mul_f64(1.25));
     }
-    
 }
@@ -1,9 +1,20 @@
 use cielo_rs_interface::*;
 
-#[tokio::main] 
+// This CLI is a native-only convenience wrapper around the library; the
+// library itself is wasm32-unknown-unknown compatible, but driving it here
+// needs tokio's runtime, so the bin target doesn't build for wasm.
+#[cfg(target_arch = "wasm32")]
+fn main() {}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
      env_logger::init(); // enables info! for debugging, used in lib.rs in URL construction
 
+     let client = CieloClient::builder()
+          .api_key(std::env::var("CIELO_API_KEY").unwrap_or_default())
+          .build();
+
      let request = CieloRequest {
           wallet: Some("GTdu7yv9DefWrEoWZnRc744qMEo5DFgrrdar7QdivEwf".to_string()),
           limit: None,
@@ -18,8 +29,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
           to_timestamp: None,
      };
 
-     let response = 
-          submit_cielo_get_request(request).await;
+     let response =
+          client.submit_cielo_get_request(request).await;
 
     match response {
         Ok(body) => {